@@ -4,20 +4,66 @@ use crate::{
     clap, Watch,
 };
 use derive_more::Debug;
+use rustls::{Certificate, PrivateKey, ServerConfig, ServerConnection, StreamOwned};
 use std::{
+    collections::HashMap,
     ffi, fs,
-    io::prelude::*,
+    io::{prelude::*, SeekFrom},
     net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream},
     path::{Path, PathBuf},
     process,
-    sync::Arc,
+    sync::{mpsc, Arc, Mutex},
     thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+/// A bidirectional connection a response can be served over, abstracting over
+/// plain TCP and TLS streams so the request handlers work unchanged over both.
+pub trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send + ?Sized> Stream for T {}
+
 type RequestHandler = Arc<
-    dyn Fn(&mut TcpStream, &str, PathBuf, Option<PathBuf>) -> Result<()> + Send + Sync + 'static,
+    dyn Fn(&mut dyn Stream, &str, PathBuf, Option<PathBuf>) -> Result<()> + Send + Sync + 'static,
 >;
 
+/// A reverse-proxy rule forwarding requests whose path starts with `prefix` to
+/// the backend at `authority` (`host:port`).
+#[derive(Clone, Debug)]
+struct Proxy {
+    prefix: String,
+    authority: String,
+}
+
+/// Reserved path serving the live-reload event stream.
+const LIVE_RELOAD_PATH: &str = "/__xtask_livereload";
+
+/// Script injected before `</body>` that reloads the page on a live-reload tick.
+const LIVE_RELOAD_SCRIPT: &str =
+    "<script>new EventSource('/__xtask_livereload').onmessage=()=>location.reload()</script>";
+
+/// Broadcast channel connecting the watch thread to the open SSE connections.
+///
+/// Each connected browser gets its own [`mpsc::Receiver`]; a rebuild sends a
+/// tick to every subscriber, dropping the ones that have disconnected.
+#[derive(Clone, Default)]
+struct LiveReload {
+    clients: Arc<Mutex<Vec<mpsc::Sender<()>>>>,
+}
+
+impl LiveReload {
+    /// Register a new SSE connection and return its tick receiver.
+    fn subscribe(&self) -> mpsc::Receiver<()> {
+        let (tx, rx) = mpsc::channel();
+        self.clients.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Notify every connected browser that a rebuild finished.
+    fn notify(&self) {
+        self.clients.lock().unwrap().retain(|tx| tx.send(()).is_ok());
+    }
+}
+
 /// A simple HTTP server useful during development.
 ///
 /// It can watch the source code for changes and restart a provided command.
@@ -75,6 +121,27 @@ pub struct DevServer {
     #[clap(long, default_value = "8000")]
     pub port: u16,
 
+    /// Reload connected browsers automatically after each successful rebuild.
+    #[clap(long)]
+    pub live_reload: bool,
+
+    /// Serve over HTTPS, generating an in-memory self-signed certificate unless
+    /// both `--tls-cert` and `--tls-key` are provided.
+    #[clap(long)]
+    pub tls: bool,
+
+    /// Generate an HTML index for directories that lack an index file.
+    #[clap(long)]
+    pub directory_listing: bool,
+
+    /// PEM-encoded certificate chain to use for HTTPS.
+    #[clap(long, requires = "tls")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM-encoded private key to use for HTTPS.
+    #[clap(long, requires = "tls")]
+    pub tls_key: Option<PathBuf>,
+
     /// Watch object for detecting changes.
     ///
     /// # Note
@@ -91,6 +158,17 @@ pub struct DevServer {
     #[clap(skip)]
     pub not_found_path: Option<PathBuf>,
 
+    /// Listen on a Unix domain socket at this path instead of a TCP port.
+    ///
+    /// A path starting with an escaped `\x00` binds in the Linux abstract
+    /// namespace.
+    #[clap(long)]
+    pub unix_socket: Option<PathBuf>,
+
+    /// Reverse-proxy rules forwarding matching paths to a backend.
+    #[clap(skip)]
+    proxies: Vec<Proxy>,
+
     /// Pass a custom request handler.
     #[clap(skip)]
     #[debug(skip)]
@@ -140,10 +218,101 @@ impl DevServer {
         self
     }
 
+    /// Reload connected browsers automatically after each successful rebuild.
+    ///
+    /// An `EventSource` script is injected into served HTML pages, and a
+    /// reserved path streams reload events over [Server-Sent Events]. Used only
+    /// if `command` is set.
+    ///
+    /// [Server-Sent Events]: https://developer.mozilla.org/docs/Web/API/Server-sent_events
+    pub fn live_reload(mut self) -> Self {
+        self.live_reload = true;
+        self
+    }
+
+    /// Serve over HTTPS.
+    ///
+    /// A self-signed certificate valid for `localhost` and the bound IP is
+    /// generated in memory for the server's lifetime, unless a certificate and
+    /// key are provided with [`DevServer::tls_cert`].
+    pub fn tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    /// Serve over HTTPS using the given PEM-encoded certificate chain and
+    /// private key instead of a generated self-signed certificate.
+    pub fn tls_cert(mut self, cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        self.tls = true;
+        self.tls_cert.replace(cert.into());
+        self.tls_key.replace(key.into());
+        self
+    }
+
+    /// Listen on a Unix domain socket at `path` instead of a TCP port.
+    ///
+    /// When `path` begins with an escaped `\x00`, the socket is bound in the
+    /// Linux abstract namespace. Useful to sit behind a front proxy without
+    /// occupying a TCP port.
+    #[cfg(unix)]
+    pub fn unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+        self.unix_socket = Some(path.into());
+        self
+    }
+
+    /// Forward requests whose path starts with `prefix` to the backend at
+    /// `target` instead of serving them from the dist directory.
+    ///
+    /// Can be called multiple times to register several backends; the first
+    /// matching prefix wins. This lets the dev server serve static assets and
+    /// proxy API calls (e.g. `/api`) without CORS workarounds.
+    pub fn proxy(mut self, prefix: impl Into<String>, target: impl Into<String>) -> Self {
+        let target = target.into();
+
+        // Derive the default port from the scheme so a target without an
+        // explicit port (e.g. `http://localhost`) still connects.
+        let (default_port, rest) = if let Some(rest) = target.strip_prefix("https://") {
+            log::warn!(
+                "proxy target {} uses https, but connections to the backend are made over plain \
+                 TCP; use an http://host:port target",
+                target,
+            );
+            (443, rest)
+        } else if let Some(rest) = target.strip_prefix("http://") {
+            (80, rest)
+        } else {
+            (80, target.as_str())
+        };
+
+        let host_port = rest.split('/').next().unwrap_or(rest);
+        let authority = if host_port.contains(':') {
+            host_port.to_string()
+        } else {
+            format!("{}:{}", host_port, default_port)
+        };
+
+        self.proxies.push(Proxy {
+            prefix: prefix.into(),
+            authority,
+        });
+        self
+    }
+
+    /// Generate an HTML directory index when a requested directory has no
+    /// `index.html`/`index.htm`, instead of returning an error.
+    ///
+    /// Entries are rendered as percent-encoded links, subdirectories gaining a
+    /// trailing `/`; a link to the parent directory is added unless at the
+    /// served root.
+    pub fn directory_listing(mut self) -> Self {
+        self.directory_listing = true;
+        self
+    }
+
     /// Pass a custom request handler to the dev server.
     pub fn request_handler<F>(mut self, handler: F) -> Self
     where
-        F: Fn(&mut TcpStream, &str, PathBuf, Option<PathBuf>) -> Result<()> + Send + Sync + 'static,
+        F: Fn(&mut dyn Stream, &str, PathBuf, Option<PathBuf>) -> Result<()> + Send + Sync + 'static,
     {
         self.request_handler.replace(Arc::new(handler));
         self
@@ -156,39 +325,83 @@ impl DevServer {
     pub fn start(self, served_path: impl Into<PathBuf>) -> Result<()> {
         let served_path = served_path.into();
 
+        let live_reload = self.live_reload.then(LiveReload::default);
+
         let watch_process = if let Some(command) = self.command {
             // NOTE: the path needs to exists in order to be excluded because it is canonicalize
             let _ = std::fs::create_dir_all(&served_path);
+
             let watch = self.watch.exclude_path(&served_path);
-            let handle = std::thread::spawn(|| match watch.run(command) {
-                Ok(()) => log::trace!("Starting to watch"),
-                Err(err) => log::error!("an error occurred when starting to watch: {}", err),
-            });
+            let handle = if let Some(live_reload) = live_reload.clone() {
+                // Reuse the same `Watch` configuration as the non-live path, but
+                // broadcast a reload tick after each rebuild completes so
+                // browsers only refresh once the build command has finished.
+                std::thread::spawn(move || match watch.run_with(command, move || live_reload.notify())
+                {
+                    Ok(()) => log::trace!("Starting to watch"),
+                    Err(err) => log::error!("an error occurred when starting to watch: {}", err),
+                })
+            } else {
+                std::thread::spawn(|| match watch.run(command) {
+                    Ok(()) => log::trace!("Starting to watch"),
+                    Err(err) => log::error!("an error occurred when starting to watch: {}", err),
+                })
+            };
 
             Some(handle)
         } else {
             None
         };
 
-        if let Some(handler) = self.request_handler {
-            serve(
-                self.ip,
-                self.port,
-                served_path,
-                self.not_found_path,
-                handler,
-            )
-            .context("an error occurred when starting to serve")?;
+        let handler = match self.request_handler {
+            Some(handler) => handler,
+            None => {
+                let inject_live_reload = self.live_reload;
+                let directory_listing = self.directory_listing;
+                Arc::new(move |stream: &mut dyn Stream, header: &str, dist, not_found| {
+                    serve_file(
+                        stream,
+                        header,
+                        dist,
+                        not_found,
+                        inject_live_reload,
+                        directory_listing,
+                    )
+                })
+            }
+        };
+
+        let handler = if self.proxies.is_empty() {
+            handler
         } else {
-            serve(
-                self.ip,
-                self.port,
-                served_path,
-                self.not_found_path,
-                Arc::new(default_request_handler),
-            )
-            .context("an error occurred when starting to serve")?;
-        }
+            let proxies = self.proxies;
+            Arc::new(
+                move |stream: &mut dyn Stream, header: &str, dist, not_found| {
+                    match proxies.iter().find(|p| request_is_proxied(header, p)) {
+                        Some(proxy) => proxy_request(stream, header, proxy),
+                        None => handler(stream, header, dist, not_found),
+                    }
+                },
+            ) as RequestHandler
+        };
+
+        let tls = if self.tls {
+            Some(tls_config(self.ip, self.tls_cert, self.tls_key)?)
+        } else {
+            None
+        };
+
+        serve(
+            self.ip,
+            self.port,
+            served_path,
+            self.not_found_path,
+            handler,
+            live_reload,
+            tls,
+            self.unix_socket,
+        )
+        .context("an error occurred when starting to serve")?;
 
         if let Some(handle) = watch_process {
             handle.join().expect("an error occurred when exiting watch");
@@ -210,62 +423,194 @@ impl Default for DevServer {
         DevServer {
             ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
             port: 8000,
+            live_reload: false,
+            tls: false,
+            directory_listing: false,
+            tls_cert: None,
+            tls_key: None,
             watch: Default::default(),
             command: None,
             not_found_path: None,
+            unix_socket: None,
+            proxies: Vec::new(),
             request_handler: None,
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn serve(
     ip: IpAddr,
     port: u16,
     served_path: PathBuf,
     not_found_path: Option<PathBuf>,
     handler: RequestHandler,
+    live_reload: Option<LiveReload>,
+    tls: Option<Arc<ServerConfig>>,
+    unix_socket: Option<PathBuf>,
 ) -> Result<()> {
+    let scheme = if tls.is_some() { "https" } else { "http" };
+
+    #[cfg(unix)]
+    if let Some(path) = unix_socket {
+        let listener = bind_unix_listener(&path)?;
+
+        log::info!(
+            "Development server ({}) running on unix socket: {}",
+            scheme,
+            path.display(),
+        );
+
+        for stream in listener.incoming().filter_map(|x| x.ok()) {
+            handle_connection(stream, &tls, &handler, &served_path, &not_found_path, &live_reload);
+        }
+
+        return Ok(());
+    }
+
+    #[cfg(not(unix))]
+    ensure!(
+        unix_socket.is_none(),
+        "unix sockets are not supported on this platform"
+    );
+
     let address = SocketAddr::new(ip, port);
     let listener = TcpListener::bind(address).context("cannot bind to the given address")?;
 
-    log::info!("Development server running at: http://{}", &address);
+    log::info!("Development server running at: {}://{}", scheme, &address);
+
+    for stream in listener.incoming().filter_map(|x| x.ok()) {
+        handle_connection(stream, &tls, &handler, &served_path, &not_found_path, &live_reload);
+    }
 
-    for mut stream in listener.incoming().filter_map(|x| x.ok()) {
-        let header = read_header(&stream)?;
-        let served_path = served_path.clone();
-        let not_found_path = not_found_path.clone();
-        let handler = handler.clone();
+    Ok(())
+}
 
-        thread::spawn(move || {
-            (handler)(&mut stream, header.as_ref(), served_path, not_found_path).unwrap_or_else(
-                |e| {
-                    let _ = stream.write("HTTP/1.1 500 INTERNAL SERVER ERROR\r\n\r\n".as_bytes());
-                    log::error!("an error occurred: {}", e);
-                },
-            );
+/// Bind a [`UnixListener`], binding in the Linux abstract namespace when the
+/// path is given as an escaped `\x00` prefix.
+///
+/// [`UnixListener`]: std::os::unix::net::UnixListener
+#[cfg(unix)]
+fn bind_unix_listener(path: &Path) -> Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::net::UnixListener;
+
+    if let Some(name) = path.to_string_lossy().strip_prefix("\\x00") {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+
+            let addr = SocketAddr::from_abstract_name(name.as_bytes())
+                .context("invalid abstract socket name")?;
+            return UnixListener::bind_addr(&addr).context("cannot bind to the abstract socket");
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = name;
+            bail!("abstract unix sockets are only supported on Linux");
+        }
+    }
+
+    // Remove a stale socket file left behind by a previous run, if any.
+    let _ = fs::remove_file(path);
+    UnixListener::bind(path).context("cannot bind to the unix socket")
+}
+
+/// Wrap a freshly accepted connection (optionally in TLS), read its request,
+/// and dispatch it to the handler on its own thread.
+fn handle_connection<S: Read + Write + Send + 'static>(
+    stream: S,
+    tls: &Option<Arc<ServerConfig>>,
+    handler: &RequestHandler,
+    served_path: &Path,
+    not_found_path: &Option<PathBuf>,
+    live_reload: &Option<LiveReload>,
+) {
+    let mut stream: Box<dyn Stream> = match tls {
+        Some(config) => match ServerConnection::new(config.clone()) {
+            Ok(conn) => Box::new(StreamOwned::new(conn, stream)),
+            Err(e) => {
+                log::error!("cannot establish TLS connection: {}", e);
+                return;
+            }
+        },
+        None => Box::new(stream),
+    };
+
+    let header = match read_header(&mut *stream) {
+        Ok(header) => header,
+        Err(e) => {
+            log::error!("cannot read request header: {}", e);
+            return;
+        }
+    };
+
+    if let Some(live_reload) = live_reload {
+        if request_target(&header) == Some(LIVE_RELOAD_PATH) {
+            let rx = live_reload.subscribe();
+            thread::spawn(move || {
+                if let Err(e) = write_live_reload_stream(&mut *stream, rx) {
+                    log::debug!("live-reload connection closed: {}", e);
+                }
+            });
+            return;
+        }
+    }
+
+    let served_path = served_path.to_path_buf();
+    let not_found_path = not_found_path.clone();
+    let handler = handler.clone();
+
+    thread::spawn(move || {
+        (handler)(&mut *stream, header.as_ref(), served_path, not_found_path).unwrap_or_else(|e| {
+            let _ = stream.write("HTTP/1.1 500 INTERNAL SERVER ERROR\r\n\r\n".as_bytes());
+            log::error!("an error occurred: {}", e);
         });
+    });
+}
+
+/// Extract the requested path (without query string) from the request line.
+fn request_target(header: &str) -> Option<&str> {
+    let target = header.lines().next()?.split_whitespace().nth(1)?;
+    Some(target.split_once('?').map_or(target, |(prefix, _)| prefix))
+}
+
+/// Hold a live-reload connection open, forwarding every rebuild tick as an SSE
+/// `reload` message.
+fn write_live_reload_stream(stream: &mut dyn Stream, rx: mpsc::Receiver<()>) -> Result<()> {
+    stream
+        .write_all(
+            "HTTP/1.1 200 OK\r\n\
+            Content-Type: text/event-stream\r\n\
+            Cache-Control: no-cache\r\n\
+            Connection: keep-alive\r\n\r\n"
+                .as_bytes(),
+        )
+        .context("cannot write live-reload response")?;
+    stream.flush()?;
+
+    while rx.recv().is_ok() {
+        stream.write_all(b"data: reload\n\n")?;
+        stream.flush()?;
     }
 
     Ok(())
 }
 
-fn read_header(mut stream: &TcpStream) -> Result<String> {
-    let mut header = Vec::with_capacity(64 * 1024);
-    let mut peek_buffer = [0u8; 4096];
+fn read_header<R: Read + ?Sized>(stream: &mut R) -> Result<String> {
+    let mut header = Vec::with_capacity(8 * 1024);
+    let mut byte = [0u8; 1];
 
+    // Read up to the end of the headers without consuming the body, which also
+    // keeps us from relying on `TcpStream::peek` now that the connection may be
+    // a TLS stream.
     loop {
-        let n = stream.peek(&mut peek_buffer)?;
+        let n = stream.read(&mut byte)?;
         ensure!(n > 0, "Unexpected EOF");
 
-        let data = &mut peek_buffer[..n];
-        if let Some(i) = data.windows(4).position(|x| x == b"\r\n\r\n") {
-            let data = &mut peek_buffer[..(i + 4)];
-            stream.read_exact(data)?;
-            header.extend(&*data);
+        header.push(byte[0]);
+        if header.ends_with(b"\r\n\r\n") {
             break;
-        } else {
-            stream.read_exact(data)?;
-            header.extend(&*data);
         }
     }
 
@@ -274,10 +619,259 @@ fn read_header(mut stream: &TcpStream) -> Result<String> {
 
 /// Default request handler.
 pub fn default_request_handler(
-    stream: &mut TcpStream,
+    stream: &mut dyn Stream,
     header: &str,
     dist_dir_path: PathBuf,
     not_found_path: Option<PathBuf>,
+) -> Result<()> {
+    serve_file(stream, header, dist_dir_path, not_found_path, false, false)
+}
+
+/// Parse the request headers (everything after the request line) into a
+/// lowercased-key map.
+fn parse_headers(header: &str) -> HashMap<String, String> {
+    header
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim().to_ascii_lowercase(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Resolve a `bytes=START-END` range spec against `total`, returning the
+/// inclusive `(start, end)` byte offsets or `None` when unsatisfiable.
+fn parse_range(value: &str, total: u64) -> Option<(u64, u64)> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+
+    let (start, end) = if start.is_empty() {
+        // Suffix range: the last `end` bytes.
+        let suffix: u64 = end.trim().parse().ok()?;
+        if suffix == 0 {
+            return None;
+        }
+        (total.saturating_sub(suffix), total.saturating_sub(1))
+    } else {
+        let start: u64 = start.trim().parse().ok()?;
+        let end = if end.trim().is_empty() {
+            total.saturating_sub(1)
+        } else {
+            end.trim().parse().ok()?
+        };
+        (start, end)
+    };
+
+    if total == 0 || start > end || end >= total {
+        return None;
+    }
+
+    Some((start, end))
+}
+
+/// Write a static-file response, honoring conditional GETs (`If-None-Match` /
+/// `If-Modified-Since`) and `Range` requests (RFC 7233).
+fn write_file_response(
+    stream: &mut dyn Stream,
+    full_path: &Path,
+    content_type: &str,
+    headers: &HashMap<String, String>,
+) -> Result<()> {
+    let metadata = full_path.metadata()?;
+    let total = metadata.len();
+    let mtime = metadata.modified().unwrap_or(UNIX_EPOCH);
+    let mtime_secs = mtime
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let etag = format!("W/\"{:x}-{:x}\"", total, mtime_secs);
+    let last_modified = http_date(mtime);
+
+    // Conditional GET: reply 304 when the client already has a fresh copy.
+    let not_modified = headers
+        .get("if-none-match")
+        .is_some_and(|v| v.split(',').any(|entry| entry.trim() == etag))
+        || headers
+            .get("if-modified-since")
+            .and_then(|v| parse_http_date(v))
+            .is_some_and(|since| {
+                since
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+                    >= mtime_secs
+            });
+
+    if not_modified {
+        log::debug!("--> 304 NOT MODIFIED");
+        stream
+            .write(
+                format!(
+                    "HTTP/1.1 304 NOT MODIFIED\r\n\
+                    ETag: {etag}\r\n\
+                    Last-Modified: {last_modified}\r\n\
+                    Accept-Ranges: bytes\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .context("cannot write response")?;
+        return Ok(());
+    }
+
+    if let Some(range) = headers.get("range").filter(|v| v.starts_with("bytes=")) {
+        return match parse_range(range, total) {
+            Some((start, end)) => {
+                let length = end - start + 1;
+                log::debug!("--> 206 PARTIAL CONTENT bytes {}-{}/{}", start, end, total);
+                stream
+                    .write(
+                        format!(
+                            "HTTP/1.1 206 PARTIAL CONTENT\r\n\
+                            Content-Length: {length}\r\n\
+                            Content-Type: {content_type}\r\n\
+                            Content-Range: bytes {start}-{end}/{total}\r\n\
+                            Accept-Ranges: bytes\r\n\
+                            ETag: {etag}\r\n\
+                            Last-Modified: {last_modified}\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .context("cannot write response")?;
+
+                let mut file = fs::File::open(full_path)?;
+                file.seek(SeekFrom::Start(start))?;
+                std::io::copy(&mut file.take(length), stream)?;
+                Ok(())
+            }
+            None => {
+                log::debug!("--> 416 RANGE NOT SATISFIABLE");
+                stream
+                    .write(
+                        format!(
+                            "HTTP/1.1 416 RANGE NOT SATISFIABLE\r\n\
+                            Content-Range: bytes */{total}\r\n\
+                            Accept-Ranges: bytes\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .context("cannot write response")?;
+                Ok(())
+            }
+        };
+    }
+
+    stream
+        .write(
+            format!(
+                "HTTP/1.1 200 OK\r\n\
+                Content-Length: {total}\r\n\
+                Content-Type: {content_type}\r\n\
+                Accept-Ranges: bytes\r\n\
+                ETag: {etag}\r\n\
+                Last-Modified: {last_modified}\r\n\r\n"
+            )
+            .as_bytes(),
+        )
+        .context("cannot write response")?;
+
+    std::io::copy(&mut fs::File::open(full_path)?, stream)?;
+
+    Ok(())
+}
+
+/// Format a [`SystemTime`] as an RFC 1123 HTTP date (always in GMT).
+fn http_date(time: SystemTime) -> String {
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (hour, minute, second) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+    let weekday = ((days % 7 + 4) % 7) as usize; // 1970-01-01 was a Thursday.
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[weekday],
+        day,
+        MONTHS[month - 1],
+        year,
+        hour,
+        minute,
+        second,
+    )
+}
+
+/// Parse an RFC 1123 HTTP date (`Wdy, DD Mon YYYY HH:MM:SS GMT`).
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let rest = value.trim().split_once(", ")?.1;
+    let mut fields = rest.split_whitespace();
+    let day: u32 = fields.next()?.parse().ok()?;
+    let month_name = fields.next()?;
+    let month = MONTHS.iter().position(|m| *m == month_name)? + 1;
+    let year: i64 = fields.next()?.parse().ok()?;
+    let mut time = fields.next()?.split(':');
+    let hour: u64 = time.next()?.parse().ok()?;
+    let minute: u64 = time.next()?.parse().ok()?;
+    let second: u64 = time.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + (hour * 3600 + minute * 60 + second) as i64;
+    u64::try_from(secs)
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Convert a count of days since the Unix epoch to a `(year, month, day)`
+/// civil date (algorithm by Howard Hinnant).
+fn civil_from_days(days: i64) -> (i64, usize, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let year = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as usize;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// Convert a `(year, month, day)` civil date to a count of days since the Unix
+/// epoch (algorithm by Howard Hinnant).
+fn days_from_civil(year: i64, month: usize, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Serve a static file, optionally injecting the live-reload script into HTML
+/// responses.
+fn serve_file(
+    stream: &mut dyn Stream,
+    header: &str,
+    dist_dir_path: PathBuf,
+    not_found_path: Option<PathBuf>,
+    inject_live_reload: bool,
+    directory_listing: bool,
 ) -> Result<()> {
     let request = header.split_whitespace().next().unwrap();
 
@@ -301,6 +895,8 @@ pub fn default_request_handler(
             full_path = full_path.join("index.html")
         } else if full_path.join("index.htm").exists() {
             full_path = full_path.join("index.htm")
+        } else if directory_listing {
+            return write_directory_listing(stream, &dist_dir_path, &full_path, requested_path);
         } else {
             bail!("no index.html in {}", full_path.display());
         }
@@ -326,18 +922,30 @@ pub fn default_request_handler(
             _ => "application/octet-stream",
         };
 
-        stream
-            .write(
-                format!(
-                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: {}\r\n\r\n",
-                    full_path.metadata()?.len(),
-                    content_type,
+        if inject_live_reload && content_type.starts_with("text/html") {
+            let mut body = fs::read(&full_path)?;
+            match body.windows(7).position(|x| x == b"</body>") {
+                Some(pos) => {
+                    body.splice(pos..pos, LIVE_RELOAD_SCRIPT.bytes());
+                }
+                None => body.extend_from_slice(LIVE_RELOAD_SCRIPT.as_bytes()),
+            }
+
+            stream
+                .write(
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: {}\r\n\r\n",
+                        body.len(),
+                        content_type,
+                    )
+                    .as_bytes(),
                 )
-                .as_bytes(),
-            )
-            .context("cannot write response")?;
+                .context("cannot write response")?;
 
-        std::io::copy(&mut fs::File::open(&full_path)?, stream)?;
+            stream.write_all(&body)?;
+        } else {
+            write_file_response(stream, &full_path, content_type, &parse_headers(header))?;
+        }
     } else {
         log::error!("--> {} (404 NOT FOUND)", full_path.display());
         stream
@@ -347,3 +955,410 @@ pub fn default_request_handler(
 
     Ok(())
 }
+
+/// Build the [`rustls`] server configuration, loading the provided certificate
+/// and key or generating a self-signed certificate when none is given.
+fn tls_config(
+    ip: IpAddr,
+    cert_path: Option<PathBuf>,
+    key_path: Option<PathBuf>,
+) -> Result<Arc<ServerConfig>> {
+    let (certs, key) = match (cert_path, key_path) {
+        (Some(cert), Some(key)) => load_certificate(&cert, &key)?,
+        _ => generate_self_signed(ip)?,
+    };
+
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("invalid certificate or private key")?;
+
+    Ok(Arc::new(config))
+}
+
+/// Generate an in-memory self-signed certificate valid for `localhost` and the
+/// bound IP, logging its SHA-256 fingerprint so the user can trust it.
+fn generate_self_signed(ip: IpAddr) -> Result<(Vec<Certificate>, PrivateKey)> {
+    let subject_alt_names = vec!["localhost".to_string(), ip.to_string()];
+    let cert = rcgen::generate_simple_self_signed(subject_alt_names)
+        .context("cannot generate a self-signed certificate")?;
+    let cert_der = cert
+        .serialize_der()
+        .context("cannot serialize the self-signed certificate")?;
+    let key_der = cert.serialize_private_key_der();
+
+    let fingerprint: String = sha256(&cert_der).iter().map(|b| format!("{:02x}", b)).collect();
+    log::info!("serving with a self-signed certificate (SHA-256: {})", fingerprint);
+
+    Ok((vec![Certificate(cert_der)], PrivateKey(key_der)))
+}
+
+/// Compute the SHA-256 digest of `data` (FIPS 180-4).
+///
+/// Implemented in-crate so the certificate fingerprint does not depend on
+/// `rustls`'s crypto backend, which is only a transitive dependency.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    #[rustfmt::skip]
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    // Pad the message to a multiple of 64 bytes: a `0x80` byte, zeroes, then the
+    // original bit length as a big-endian u64.
+    let mut msg = data.to_vec();
+    let bit_len = (data.len() as u64) * 8;
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in msg.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let mut v = h;
+        for i in 0..64 {
+            let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+            let ch = (v[4] & v[5]) ^ ((!v[4]) & v[6]);
+            let t1 = v[7]
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+            let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+            let t2 = s0.wrapping_add(maj);
+
+            v[7] = v[6];
+            v[6] = v[5];
+            v[5] = v[4];
+            v[4] = v[3].wrapping_add(t1);
+            v[3] = v[2];
+            v[2] = v[1];
+            v[1] = v[0];
+            v[0] = t1.wrapping_add(t2);
+        }
+
+        for (acc, value) in h.iter_mut().zip(v) {
+            *acc = acc.wrapping_add(value);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// Load a PEM-encoded certificate chain and private key from disk.
+fn load_certificate(cert_path: &Path, key_path: &Path) -> Result<(Vec<Certificate>, PrivateKey)> {
+    let cert_pem = fs::read(cert_path).context("cannot read the certificate")?;
+    let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+        .context("cannot parse the certificate")?
+        .into_iter()
+        .map(Certificate)
+        .collect();
+
+    let key_pem = fs::read(key_path).context("cannot read the private key")?;
+    let key = rustls_pemfile::pkcs8_private_keys(&mut key_pem.as_slice())
+        .context("cannot parse the private key")?
+        .into_iter()
+        .next()
+        .context("no private key found")?;
+
+    Ok((certs, PrivateKey(key)))
+}
+
+/// Whether the request described by `header` matches a reverse-proxy rule.
+fn request_is_proxied(header: &str, proxy: &Proxy) -> bool {
+    let prefix = proxy.prefix.trim_end_matches('/');
+    request_target(header).is_some_and(|target| {
+        // Match on a path-segment boundary so `/api` does not capture
+        // `/apiary` or `/apidocs`.
+        target == prefix
+            || target
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Rebuild the request head for the backend, rewriting the `Host` header to the
+/// proxy target and forcing `Connection: close` so a keep-alive backend closes
+/// the socket once the response is sent.
+fn build_proxied_head(header: &str, proxy: &Proxy) -> String {
+    let mut lines = header.lines();
+    let mut head = String::new();
+
+    if let Some(request_line) = lines.next() {
+        head.push_str(request_line);
+        head.push_str("\r\n");
+    }
+
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+
+        let lowercase = line.to_ascii_lowercase();
+        if lowercase.starts_with("host:") {
+            head.push_str(&format!("Host: {}\r\n", proxy.authority));
+        } else if lowercase.starts_with("connection:") || lowercase.starts_with("keep-alive:") {
+            // Dropped; replaced by the forced `Connection: close` below.
+            continue;
+        } else {
+            head.push_str(line);
+            head.push_str("\r\n");
+        }
+    }
+
+    head.push_str("Connection: close\r\n\r\n");
+    head
+}
+
+/// Forward a request to a reverse-proxy backend and relay its response verbatim.
+fn proxy_request(stream: &mut dyn Stream, header: &str, proxy: &Proxy) -> Result<()> {
+    log::debug!("proxying {:?} to {}", request_target(header), proxy.authority);
+
+    let mut backend = TcpStream::connect(&proxy.authority)
+        .with_context(|| format!("cannot connect to proxy target {}", proxy.authority))?;
+
+    backend
+        .write_all(build_proxied_head(header, proxy).as_bytes())
+        .context("cannot forward request to proxy target")?;
+
+    // Forward the request body, if the client announced one.
+    if let Some(len) = parse_headers(header)
+        .get("content-length")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    {
+        std::io::copy(&mut (&mut *stream).take(len), &mut backend)?;
+    }
+    backend.flush()?;
+
+    // Relay the backend response (status line, headers and body) verbatim.
+    std::io::copy(&mut backend, stream)?;
+
+    Ok(())
+}
+
+/// Render and write an HTML index for a directory without an index file.
+///
+/// The resolved directory is canonicalized and checked to stay within
+/// `dist_dir_path` to guard against path traversal.
+fn write_directory_listing(
+    stream: &mut dyn Stream,
+    dist_dir_path: &Path,
+    dir_path: &Path,
+    requested_path: &str,
+) -> Result<()> {
+    let root = dist_dir_path
+        .canonicalize()
+        .context("cannot canonicalize the served directory")?;
+    let current = dir_path
+        .canonicalize()
+        .context("cannot canonicalize the requested directory")?;
+    ensure!(
+        current.starts_with(&root),
+        "refusing to list a directory outside of the served path"
+    );
+
+    // The links are built on the requested path, so the base needs a trailing
+    // slash.
+    let base = if requested_path.ends_with('/') {
+        requested_path.to_string()
+    } else {
+        format!("{}/", requested_path)
+    };
+
+    // Links are built from `base`, so its own path segments need percent-encoding
+    // too; otherwise a directory whose name contains spaces or control characters
+    // produces broken links.
+    let href_base = percent_encode_path(&base);
+
+    let mut entries: Vec<_> = fs::read_dir(dir_path)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut body = String::new();
+    body.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+    body.push_str(&format!("<title>Index of {}</title></head>\n", html_escape(&base)));
+    body.push_str(&format!("<body><h1>Index of {}</h1>\n<ul>\n", html_escape(&base)));
+
+    if current != root {
+        body.push_str(&format!(
+            "<li><a href=\"{}../\">../</a></li>\n",
+            html_escape(&href_base)
+        ));
+    }
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        let suffix = if is_dir { "/" } else { "" };
+        let size = if is_dir {
+            String::new()
+        } else {
+            format!(" ({} bytes)", entry.metadata().map(|m| m.len()).unwrap_or(0))
+        };
+
+        body.push_str(&format!(
+            "<li><a href=\"{base}{href}{suffix}\">{name}{suffix}</a>{size}</li>\n",
+            base = html_escape(&href_base),
+            href = percent_encode(&name),
+            name = html_escape(&name),
+        ));
+    }
+
+    body.push_str("</ul></body></html>");
+
+    stream
+        .write(
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: text/html;charset=utf-8\r\n\r\n",
+                body.len(),
+            )
+            .as_bytes(),
+        )
+        .context("cannot write response")?;
+    stream.write_all(body.as_bytes())?;
+
+    Ok(())
+}
+
+/// Percent-encode a path segment, leaving the unreserved characters as-is.
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for &byte in input.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Percent-encode each segment of a `/`-separated path, keeping the separators
+/// intact so the result is still a usable relative URL.
+fn percent_encode_path(path: &str) -> String {
+    path.split('/')
+        .map(percent_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Escape the characters that are significant in HTML text and attributes.
+fn html_escape(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_variants() {
+        assert_eq!(parse_range("bytes=0-49", 100), Some((0, 49)));
+        assert_eq!(parse_range("bytes=50-", 100), Some((50, 99)));
+        assert_eq!(parse_range("bytes=-20", 100), Some((80, 99)));
+        assert_eq!(parse_range("bytes=99-99", 100), Some((99, 99)));
+
+        // Suffix larger than the file clamps to the whole file.
+        assert_eq!(parse_range("bytes=-200", 100), Some((0, 99)));
+
+        // Invalid or unsatisfiable ranges.
+        assert_eq!(parse_range("bytes=-0", 100), None);
+        assert_eq!(parse_range("bytes=100-200", 100), None);
+        assert_eq!(parse_range("bytes=60-50", 100), None);
+        assert_eq!(parse_range("bytes=abc", 100), None);
+        assert_eq!(parse_range("items=0-10", 100), None);
+        assert_eq!(parse_range("bytes=0-0", 0), None);
+    }
+
+    #[test]
+    fn http_date_known_value() {
+        assert_eq!(http_date(UNIX_EPOCH), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(
+            http_date(UNIX_EPOCH + Duration::from_secs(784_111_777)),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+    }
+
+    #[test]
+    fn http_date_round_trip() {
+        for secs in [0, 1, 86_400, 784_111_777, 1_700_000_000, 4_102_444_800] {
+            let time = UNIX_EPOCH + Duration::from_secs(secs);
+            assert_eq!(parse_http_date(&http_date(time)), Some(time));
+        }
+    }
+
+    #[test]
+    fn civil_days_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+
+        for days in [-719_162, -1, 0, 1, 9_862, 19_660, 47_846, 853_128] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn percent_encode_escapes_reserved() {
+        assert_eq!(percent_encode("foo bar"), "foo%20bar");
+        assert_eq!(percent_encode("a/b?c"), "a%2Fb%3Fc");
+        assert_eq!(percent_encode("aZ09-_.~"), "aZ09-_.~");
+    }
+
+    #[test]
+    fn sha256_known_vectors() {
+        let hex = |data: &[u8]| sha256(data).iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(
+            hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn percent_encode_path_keeps_separators() {
+        assert_eq!(percent_encode_path("/my dir/sub/"), "/my%20dir/sub/");
+        assert_eq!(percent_encode_path("/"), "/");
+    }
+}